@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// An opaque identifier for an e-class, used as an index into [`unionfind::UnionFind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Id(usize);
+
+impl From<usize> for Id {
+    fn from(u: usize) -> Self {
+        Id(u)
+    }
+}
+
+impl From<Id> for usize {
+    fn from(id: Id) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+mod unionfind;
+pub use unionfind::{ConcurrentUnionFind, UnionFind};
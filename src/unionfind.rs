@@ -1,20 +1,98 @@
 use crate::Id;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::hash::Hash;
 
-#[derive(Debug, Clone, Default)]
+/// An entry in `UnionFind`'s undo log, recording just enough state to reverse one mutation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
+enum Undo {
+    MakeSet,
+    Union {
+        child: Id,
+        root: Id,
+        old_parent: Id,
+        moved_members: Vec<Id>,
+    },
+    Split {
+        leader_id: Id,
+        newly_deprecated: bool,
+        old_leader_members: Vec<Id>,
+        appended: Vec<(Id, usize)>,
+        old_parents: Vec<(Id, Id)>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde-1", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnionFind {
     parents: Vec<Id>,
     deprecated_leaders: Vec<Id>,
+    /// Dense membership test for `deprecated_leaders`, indexed by `usize::from(Id)`, kept in
+    /// sync with it so `find`/`find_mut` don't pay an O(k) scan on every step of every walk.
+    is_deprecated: Vec<bool>,
+    /// Reverse index from a leader to every id currently assigned to its eclass, indexed by
+    /// `usize::from(Id)`. Only the entry for a current leader is meaningful; non-leader slots
+    /// are left empty. Kept in sync by `make_set`, `union` and `split` so callers don't have to
+    /// independently track cluster membership.
+    members: Vec<Vec<Id>>,
+    /// Undo log for `checkpoint`/`rollback`, letting incremental search backtrack over unions
+    /// and splits.
+    log: Vec<Undo>,
 }
 
 impl UnionFind {
     pub fn make_set(&mut self) -> Id {
         let id = Id::from(self.parents.len());
         self.parents.push(id);
+        self.is_deprecated.push(false);
+        self.members.push(vec![id]);
+        self.log.push(Undo::MakeSet);
         id
     }
 
+    /// Returns a token representing the current state, to later `rollback` to.
+    pub fn checkpoint(&self) -> usize {
+        self.log.len()
+    }
+
+    /// Undoes every union and split performed since `checkpoint`, restoring the structure to be
+    /// byte-identical to its state at that point. `checkpoint` must have been returned by an
+    /// earlier call to `Self::checkpoint` on this union-find.
+    pub fn rollback(&mut self, checkpoint: usize) {
+        while self.log.len() > checkpoint {
+            match self.log.pop().unwrap() {
+                Undo::MakeSet => {
+                    self.parents.pop();
+                    self.is_deprecated.pop();
+                    self.members.pop();
+                }
+                Undo::Union { child, root, old_parent, moved_members } => {
+                    *self.parent_mut(child) = old_parent;
+                    let root_members = &mut self.members[usize::from(root)];
+                    let kept = root_members.len() - moved_members.len();
+                    root_members.truncate(kept);
+                    self.members[usize::from(child)] = moved_members;
+                }
+                Undo::Split { leader_id, newly_deprecated, old_leader_members, appended, old_parents } => {
+                    for (member, old_parent) in old_parents.into_iter().rev() {
+                        *self.parent_mut(member) = old_parent;
+                    }
+                    for (new_leader, appended_len) in appended.into_iter().rev() {
+                        let new_leader_members = &mut self.members[usize::from(new_leader)];
+                        let kept = new_leader_members.len() - appended_len;
+                        new_leader_members.truncate(kept);
+                    }
+                    self.members[usize::from(leader_id)] = old_leader_members;
+                    if newly_deprecated {
+                        self.deprecated_leaders.pop();
+                        self.is_deprecated[usize::from(leader_id)] = false;
+                    }
+                }
+            }
+        }
+    }
+
     pub fn size(&self) -> usize {
         self.parents.len()
     }
@@ -27,13 +105,17 @@ impl UnionFind {
         &mut self.parents[usize::from(query)]
     }
 
+    fn is_deprecated(&self, query: Id) -> bool {
+        self.is_deprecated[usize::from(query)]
+    }
+
     pub fn find(&self, mut current: Id) -> Id {
         let original = current;
-        if self.deprecated_leaders.contains(&current) {
+        if self.is_deprecated(current) {
             panic!("Trying to find a deprecated leader <{}> in the eclass <{}>", current, original);
         }
         while current != self.parent(current) {
-            if self.deprecated_leaders.contains(&current) {
+            if self.is_deprecated(current) {
                 panic!("Deprecated leader <{}> found in the eclass <{}>", current, original);
             }
             current = self.parent(current)
@@ -43,11 +125,11 @@ impl UnionFind {
 
     pub fn find_mut(&mut self, mut current: Id) -> Id {
         let original = current;
-        if self.deprecated_leaders.contains(&current) {
+        if self.is_deprecated(current) {
             panic!("Trying to find a deprecated leader <{}> in the eclass <{}>", current, original);
         }
         while current != self.parent(current) {
-            if self.deprecated_leaders.contains(&current) {
+            if self.is_deprecated(current) {
                 panic!("Deprecated leader <{}> found in the eclass <{}>", current, original);
             }
             let grandparent = self.parent(self.parent(current));
@@ -59,23 +141,434 @@ impl UnionFind {
 
     /// Given two leader ids, unions the two eclasses making root1 the leader.
     pub fn union(&mut self, root1: Id, root2: Id) -> Id {
+        let old_parent = self.parent(root2);
         *self.parent_mut(root2) = root1;
+        let moved = std::mem::take(&mut self.members[usize::from(root2)]);
+        self.members[usize::from(root1)].extend(moved.iter().copied());
+        self.log.push(Undo::Union { child: root2, root: root1, old_parent, moved_members: moved });
         root1
     }
 
     /// Given the deprecated leader id and the new clusters, updates the parents.
     pub fn split(&mut self, leader_id: Id, clusters: Vec<(Id, Vec<Id>)>) {
-        if !self.deprecated_leaders.contains(&leader_id) {
+        let newly_deprecated = !self.is_deprecated(leader_id);
+        if newly_deprecated {
             self.deprecated_leaders.push(leader_id);
+            self.is_deprecated[usize::from(leader_id)] = true;
         }
+        let old_leader_members = std::mem::take(&mut self.members[usize::from(leader_id)]);
+        let mut appended = Vec::new();
+        let mut old_parents = Vec::new();
         for (new_leader, members) in clusters {
-            for member in members {
+            appended.push((new_leader, members.len()));
+            for &member in &members {
+                old_parents.push((member, self.parent(member)));
                 *self.parent_mut(member) = new_leader;
             }
+            self.members[usize::from(new_leader)].extend(members);
+        }
+        self.log.push(Undo::Split {
+            leader_id,
+            newly_deprecated,
+            old_leader_members,
+            appended,
+            old_parents,
+        });
+    }
+
+    /// Returns every id currently assigned to `leader`'s eclass, including `leader` itself.
+    pub fn members_of(&self, leader: Id) -> impl Iterator<Item = Id> + '_ {
+        self.members[usize::from(leader)].iter().copied()
+    }
+
+    /// Partitions `leader`'s eclass by `group_key`, giving each distinct key a freshly made
+    /// leader, and splits the class accordingly. Returns the new leaders, in the order their
+    /// groups were first seen. This is `split` for callers that only know how to classify
+    /// members, not how to reconstruct the exact cluster list `split` expects.
+    pub fn split_by<K, F>(&mut self, leader: Id, mut group_key: F) -> Vec<Id>
+    where
+        K: Eq + Hash + Clone,
+        F: FnMut(Id) -> K,
+    {
+        let mut groups: HashMap<K, Vec<Id>> = HashMap::new();
+        let mut order = Vec::new();
+        for member in self.members_of(leader).collect::<Vec<_>>() {
+            let key = group_key(member);
+            if !groups.contains_key(&key) {
+                order.push(key.clone());
+            }
+            groups.entry(key).or_default().push(member);
+        }
+        let clusters: Vec<(Id, Vec<Id>)> = order
+            .into_iter()
+            .map(|key| (self.make_set(), groups.remove(&key).unwrap()))
+            .collect();
+        let new_leaders = clusters.iter().map(|(new_leader, _)| *new_leader).collect();
+        self.split(leader, clusters);
+        new_leaders
+    }
+
+    /// Serializes `parents` and `deprecated_leaders` into the flat format `open_mmap` reads,
+    /// for zero-copy loading of very large union-finds.
+    #[cfg(feature = "mmap")]
+    pub fn save_mmap(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        mmap::save(self, path.as_ref())
+    }
+
+    /// Maps `path` (written by `save_mmap`) and returns a read-only, zero-copy view over it.
+    #[cfg(feature = "mmap")]
+    pub fn open_mmap(path: impl AsRef<std::path::Path>) -> std::io::Result<mmap::MmapUnionFind> {
+        mmap::MmapUnionFind::open(path.as_ref())
+    }
+}
+
+/// A union-find that many threads can mutate concurrently without a global lock, for parallel
+/// e-graph construction.
+///
+/// `find` walks parent pointers with path-halving implemented via compare-and-swap, and `union`
+/// always makes the smaller id the canonical root so merges stay deterministic regardless of
+/// which thread races in first. Growth (`make_set`) takes a write lock since `Vec` reallocation
+/// isn't atomic; `find` and `union` only ever take the read lock, so they never block each other.
+///
+/// `split` and leader deprecation are unsupported here: retiring a leader concurrently with
+/// CAS-based links is unsound, so use [`UnionFind`] if you need that.
+#[derive(Debug, Default)]
+pub struct ConcurrentUnionFind {
+    parents: std::sync::RwLock<Vec<std::sync::atomic::AtomicUsize>>,
+}
+
+impl ConcurrentUnionFind {
+    pub fn make_set(&self) -> Id {
+        use std::sync::atomic::AtomicUsize;
+
+        let mut parents = self.parents.write().unwrap();
+        let id = parents.len();
+        parents.push(AtomicUsize::new(id));
+        Id::from(id)
+    }
+
+    pub fn size(&self) -> usize {
+        self.parents.read().unwrap().len()
+    }
+
+    /// Finds the canonical id for `current`, path-halving via CAS as it walks. CAS failures are
+    /// ignored and the walk just continues from the pointer it read; another thread has already
+    /// made equivalent progress.
+    pub fn find(&self, current: Id) -> Id {
+        use std::sync::atomic::Ordering;
+
+        let parents = self.parents.read().unwrap();
+        let mut x = usize::from(current);
+        loop {
+            let p = parents[x].load(Ordering::Acquire);
+            if p == x {
+                return Id::from(x);
+            }
+            let gp = parents[p].load(Ordering::Acquire);
+            if gp != p {
+                let _ = parents[x].compare_exchange(p, gp, Ordering::AcqRel, Ordering::Relaxed);
+            }
+            x = gp;
+        }
+    }
+
+    /// Unions the eclasses of `a` and `b`. The smaller id is always chosen as the canonical root
+    /// to preserve egg's determinism. Retries under contention from concurrent unions of
+    /// overlapping classes.
+    pub fn union(&self, a: Id, b: Id) {
+        use std::sync::atomic::Ordering;
+
+        loop {
+            let ra = usize::from(self.find(a));
+            let rb = usize::from(self.find(b));
+            if ra == rb {
+                return;
+            }
+            let (small, large) = if ra < rb { (ra, rb) } else { (rb, ra) };
+            let parents = self.parents.read().unwrap();
+            if parents[large]
+                .compare_exchange(large, small, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+            // `large`'s parent moved under us; retry from the top.
+        }
+    }
+}
+
+/// Zero-copy mmap-backed persistence for `UnionFind`, for loading multi-gigabyte e-graphs
+/// without materializing `parents` in memory.
+///
+/// The on-disk format is a fixed-size header, a flat little-endian `u64` array (one entry per
+/// id, `parents`), and a dense bitset (one bit per id, set iff that id is a deprecated leader).
+/// The header records the id count and the byte offset the bitset starts at, so a reader can map
+/// the file and index straight into it without parsing anything else. The bitset keeps the
+/// deprecated-leader check O(1), the same way `UnionFind::is_deprecated` does in memory.
+#[cfg(feature = "mmap")]
+pub mod mmap {
+    use super::{Id, UnionFind};
+    use memmap2::Mmap;
+    use std::convert::TryInto;
+    use std::fs::File;
+    use std::io::{self, Write};
+    use std::path::Path;
+
+    const MAGIC: [u8; 4] = *b"EGUF";
+    const VERSION: u8 = 1;
+    const HEADER_LEN: usize = 24;
+
+    fn bitset_len(len: usize) -> usize {
+        len.div_ceil(8)
+    }
+
+    fn write_header(out: &mut impl Write, len: u64, bitset_offset: u64) -> io::Result<()> {
+        out.write_all(&MAGIC)?;
+        out.write_all(&[VERSION])?;
+        out.write_all(&[0u8; 3])?; // padding, keeps the u64 fields 8-byte aligned
+        out.write_all(&len.to_le_bytes())?;
+        out.write_all(&bitset_offset.to_le_bytes())?;
+        Ok(())
+    }
+
+    pub(super) fn save(uf: &UnionFind, path: &Path) -> io::Result<()> {
+        let len = uf.parents.len();
+        let bitset_offset = (HEADER_LEN as u64) + (len as u64) * 8;
+
+        let mut file = File::create(path)?;
+        write_header(&mut file, len as u64, bitset_offset)?;
+        for id in &uf.parents {
+            file.write_all(&(usize::from(*id) as u64).to_le_bytes())?;
+        }
+        let mut bitset = vec![0u8; bitset_len(len)];
+        for (i, deprecated) in uf.is_deprecated.iter().enumerate() {
+            if *deprecated {
+                bitset[i / 8] |= 1 << (i % 8);
+            }
+        }
+        file.write_all(&bitset)?;
+        Ok(())
+    }
+
+    /// A read-only, zero-copy view over a `UnionFind` persisted with `UnionFind::save_mmap`.
+    ///
+    /// `find` reads straight off the mapped region. Path compression and all other mutators are
+    /// unsupported: the backing pages are a shared, read-only mapping, so mutating them would
+    /// either corrupt the file or require a private copy-on-write page this type doesn't take.
+    pub struct MmapUnionFind {
+        mmap: Mmap,
+        len: usize,
+        bitset_offset: usize,
+    }
+
+    /// Returned by `find_mut` to signal that this is a read-only, mmap-backed union-find.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ReadOnlyError;
+
+    impl std::fmt::Display for ReadOnlyError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "MmapUnionFind is read-only; load it into a UnionFind to mutate it")
+        }
+    }
+
+    impl std::error::Error for ReadOnlyError {}
+
+    fn invalid_data(msg: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+    }
+
+    impl MmapUnionFind {
+        pub fn open(path: &Path) -> io::Result<Self> {
+            let file = File::open(path)?;
+            let mmap = unsafe { Mmap::map(&file)? };
+            if mmap.len() < HEADER_LEN || mmap[0..4] != MAGIC {
+                return Err(invalid_data("not an EGUF union-find file"));
+            }
+            if mmap[4] != VERSION {
+                return Err(invalid_data("unsupported EGUF version"));
+            }
+            let len_u64 = u64::from_le_bytes(mmap[8..16].try_into().unwrap());
+            let bitset_offset_u64 = u64::from_le_bytes(mmap[16..24].try_into().unwrap());
+
+            // `len`/`bitset_offset` come straight off an untrusted file; validate with checked
+            // arithmetic so a corrupted (but not truncated) header fails with an `io::Error`
+            // instead of panicking on out-of-bounds slice indexing later in `parent`/`find`.
+            let len: usize = len_u64.try_into().map_err(|_| invalid_data("EGUF id count overflows usize"))?;
+            let bitset_offset: usize = bitset_offset_u64
+                .try_into()
+                .map_err(|_| invalid_data("EGUF bitset offset overflows usize"))?;
+            let parents_len = len.checked_mul(8).ok_or_else(|| invalid_data("EGUF parents array is too large"))?;
+            let parents_end =
+                HEADER_LEN.checked_add(parents_len).ok_or_else(|| invalid_data("EGUF parents array is too large"))?;
+            if bitset_offset < parents_end {
+                return Err(invalid_data("EGUF bitset offset overlaps the parents array"));
+            }
+            let bitset_end = bitset_offset
+                .checked_add(bitset_len(len))
+                .ok_or_else(|| invalid_data("EGUF bitset is too large"))?;
+            if mmap.len() < bitset_end {
+                return Err(invalid_data("truncated EGUF union-find file"));
+            }
+            Ok(Self { mmap, len, bitset_offset })
+        }
+
+        pub fn size(&self) -> usize {
+            self.len
+        }
+
+        fn read_u64(&self, byte_offset: usize) -> u64 {
+            u64::from_le_bytes(self.mmap[byte_offset..byte_offset + 8].try_into().unwrap())
+        }
+
+        pub fn parent(&self, query: Id) -> Id {
+            Id::from(self.read_u64(HEADER_LEN + usize::from(query) * 8) as usize)
+        }
+
+        fn is_deprecated(&self, query: Id) -> bool {
+            let i = usize::from(query);
+            let byte = self.mmap[self.bitset_offset + i / 8];
+            byte & (1 << (i % 8)) != 0
+        }
+
+        pub fn find(&self, mut current: Id) -> Id {
+            let original = current;
+            if self.is_deprecated(current) {
+                panic!("Trying to find a deprecated leader <{}> in the eclass <{}>", current, original);
+            }
+            while current != self.parent(current) {
+                if self.is_deprecated(current) {
+                    panic!("Deprecated leader <{}> found in the eclass <{}>", current, original);
+                }
+                current = self.parent(current);
+            }
+            current
+        }
+
+        /// Always fails: path compression requires mutating the mapped pages, which this
+        /// read-only view doesn't support. Use `find` instead.
+        pub fn find_mut(&mut self, _current: Id) -> Result<Id, ReadOnlyError> {
+            Err(ReadOnlyError)
         }
     }
 }
 
+#[cfg(all(test, feature = "mmap"))]
+mod mmap_tests {
+    use super::mmap::ReadOnlyError;
+    use super::UnionFind;
+    use crate::Id;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("egg_increm_unionfind_{}_{}.bin", std::process::id(), name))
+    }
+
+    #[test]
+    fn save_and_open_mmap_round_trip() {
+        let mut uf = UnionFind::default();
+        for _ in 0..4 {
+            uf.make_set();
+        }
+        uf.union(Id::from(0), Id::from(1));
+        uf.union(Id::from(0), Id::from(2));
+        let id4 = uf.make_set();
+        let id5 = uf.make_set();
+        uf.split(Id::from(0), vec![(id4, vec![Id::from(1)]), (id5, vec![Id::from(2)])]);
+
+        let path = temp_path("round_trip");
+        uf.save_mmap(&path).unwrap();
+        let mapped = UnionFind::open_mmap(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(mapped.size(), uf.size());
+        assert_eq!(mapped.find(Id::from(1)), uf.find(Id::from(1)));
+        assert_eq!(mapped.find(Id::from(2)), uf.find(Id::from(2)));
+        assert_eq!(mapped.find(Id::from(3)), uf.find(Id::from(3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Trying to find a deprecated leader <0>")]
+    fn mmap_find_panics_on_deprecated_leader() {
+        let mut uf = UnionFind::default();
+        let id0 = uf.make_set();
+        let id1 = uf.make_set();
+        uf.union(id0, id1);
+        let id2 = uf.make_set();
+        uf.split(id0, vec![(id2, vec![id1])]);
+
+        let path = temp_path("deprecated_leader");
+        uf.save_mmap(&path).unwrap();
+        let mapped = UnionFind::open_mmap(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        mapped.find(id0);
+    }
+
+    #[test]
+    fn mmap_find_mut_is_read_only() {
+        let mut uf = UnionFind::default();
+        uf.make_set();
+
+        let path = temp_path("read_only");
+        uf.save_mmap(&path).unwrap();
+        let mut mapped = UnionFind::open_mmap(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(mapped.find_mut(Id::from(0)), Err(ReadOnlyError));
+    }
+
+    #[test]
+    fn open_mmap_rejects_malformed_header() {
+        let path = temp_path("malformed");
+        std::fs::write(&path, b"not a union-find file").unwrap();
+
+        let result = UnionFind::open_mmap(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn open_mmap_rejects_truncated_file() {
+        let mut uf = UnionFind::default();
+        for _ in 0..8 {
+            uf.make_set();
+        }
+
+        let path = temp_path("truncated");
+        uf.save_mmap(&path).unwrap();
+        let full_len = std::fs::metadata(&path).unwrap().len();
+        let truncated = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &truncated[..(full_len as usize - 1)]).unwrap();
+
+        let result = UnionFind::open_mmap(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn open_mmap_rejects_corrupted_len_without_panicking() {
+        let mut uf = UnionFind::default();
+        for _ in 0..8 {
+            uf.make_set();
+        }
+
+        let path = temp_path("corrupted-len");
+        uf.save_mmap(&path).unwrap();
+        let mut bytes = std::fs::read(&path).unwrap();
+        // Corrupt `len` to a huge value; the parents array size `len * 8` overflows,
+        // so this must be rejected as `InvalidData` rather than panicking on
+        // out-of-bounds indexing once `parent`/`is_deprecated` read the mapped file.
+        bytes[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+
+        let result = UnionFind::open_mmap(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +716,102 @@ mod tests {
         let expected_deprecated_leaders = vec![id1, id6];
         assert_eq!(uf.deprecated_leaders, expected_deprecated_leaders);
     }
+
+    #[test]
+    fn members_of_and_split_by() {
+        let mut uf = UnionFind::default();
+        let id0 = uf.make_set();
+        let id1 = uf.make_set();
+        let id2 = uf.make_set();
+        let id3 = uf.make_set();
+        uf.union(id0, id1);
+        uf.union(id0, id2);
+        uf.union(id0, id3);
+
+        let mut members = uf.members_of(id0).collect::<Vec<_>>();
+        members.sort();
+        assert_eq!(members, ids(0..4));
+
+        // split the class into evens and odds
+        let new_leaders = uf.split_by(id0, |id| usize::from(id) % 2);
+        assert_eq!(new_leaders.len(), 2);
+
+        let evens = new_leaders
+            .iter()
+            .find(|&&leader| uf.members_of(leader).any(|id| id == id0))
+            .copied()
+            .unwrap();
+        let odds = new_leaders.into_iter().find(|&leader| leader != evens).unwrap();
+
+        // the new leader is itself a fresh id, so it's a member of its own group alongside
+        // whichever original ids landed in it
+        let mut evens_members = uf.members_of(evens).collect::<Vec<_>>();
+        evens_members.sort();
+        let mut expected_evens = ids([0, 2]);
+        expected_evens.push(evens);
+        expected_evens.sort();
+        assert_eq!(evens_members, expected_evens);
+
+        let mut odds_members = uf.members_of(odds).collect::<Vec<_>>();
+        odds_members.sort();
+        let mut expected_odds = ids([1, 3]);
+        expected_odds.push(odds);
+        expected_odds.sort();
+        assert_eq!(odds_members, expected_odds);
+
+        assert_eq!(uf.find(id2), evens);
+        assert_eq!(uf.find(id1), odds);
+        assert_eq!(uf.find(id3), odds);
+    }
+
+    #[test]
+    fn concurrent_union_find() {
+        let n = 100;
+        let uf = ConcurrentUnionFind::default();
+        for _ in 0..n {
+            uf.make_set();
+        }
+
+        std::thread::scope(|scope| {
+            let uf = &uf;
+            for i in 0..n - 1 {
+                scope.spawn(move || {
+                    uf.union(Id::from(i), Id::from(i + 1));
+                });
+            }
+        });
+
+        let root = uf.find(Id::from(0));
+        for i in 0..n {
+            assert_eq!(uf.find(Id::from(i)), root);
+        }
+        // the smallest id in the fully-merged class is always the canonical root
+        assert_eq!(root, Id::from(0));
+    }
+
+    #[test]
+    fn checkpoint_rollback() {
+        let mut uf = UnionFind::default();
+        let id0 = uf.make_set();
+        let id1 = uf.make_set();
+        let id2 = uf.make_set();
+
+        let before = uf.clone();
+        let checkpoint = uf.checkpoint();
+
+        let id3 = uf.make_set();
+        uf.union(id0, id1);
+        uf.union(id0, id2);
+
+        let id4 = uf.make_set();
+        let id5 = uf.make_set();
+        uf.split(id0, vec![(id4, vec![id1]), (id5, vec![id2])]);
+
+        assert_eq!(uf.find(id3), id3);
+        assert_eq!(uf.find(id1), id4);
+        assert_eq!(uf.find(id2), id5);
+
+        uf.rollback(checkpoint);
+        assert_eq!(uf, before);
+    }
 }